@@ -9,12 +9,30 @@
 //!
 //!  - Do nothing if kernel argument contains `net.ifnames=0` which disabled the
 //!    predictable network interface name, hence not fit our use case here.
-//!  - Iterate over all active NICs
-//!  - Pin every Ethernet interface to its MAC address (prefer permanent MAC
-//!    address) using link files and the [`ifname=`] kernel argument.
-//!  - After booting to new environment, use `udevadm test-builtin net_id` to
-//!    check whether pined interface name is different from systemd UDEV
-//!    Generated one. If still the same, remove the `.link` file.
+//!  - Iterate over all active, persistable interfaces -- not just physical
+//!    Ethernet NICs, but also bonds, bridges, VLANs and tun/tap devices
+//!  - Pin each one using a [`LinkMatch`]: a `[Match]` block built from the
+//!    sysfs-derived selectors available for the device -- its bus path or
+//!    its permanent MAC address; only when neither is used, its driver and
+//!    device type; and only when those are missing too, its current name --
+//!    so even MAC-less devices such as bonds, bridges, VLANs and tun/tap
+//!    devices can be pinned.
+//!  - After booting to new environment, check the udev database (falling
+//!    back to `udevadm test-builtin net_id` when it is missing or
+//!    incomplete) to see whether the pined interface name is different
+//!    from the systemd UDEV generated one. If still the same, remove the
+//!    `.link` file.
+//!  - A naming-scheme upgrade can also make the *predicted* name itself
+//!    drift out from under an existing pin. `Verify` detects that and
+//!    re-pins the device to the newly predicted name: it rewrites the
+//!    `.link` file's `Name=`/`AlternativeName=` (replacing the file, since
+//!    its name encodes the pinned name) and regenerates the corresponding
+//!    `ifname=` karg.
+//!
+//! Link and MAC address enumeration uses the full nmstate retrieval path
+//! and falls back to a direct netlink query when that retrieval fails, so
+//! this also works in offline/image-build contexts where the usual
+//! retrieval path isn't available.
 //!
 //! [`.link`]: https://www.freedesktop.org/software/systemd/man/systemd.link.html
 //! [`ifname=`]: https://www.man7.org/linux/man-pages/man7/dracut.cmdline.7.html
@@ -22,7 +40,8 @@ use std::collections::HashMap;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 
-use nmstate::{InterfaceType, NetworkState};
+use futures::stream::TryStreamExt;
+use nmstate::{Interface, InterfaceType, NetworkState};
 
 use crate::error::CliError;
 
@@ -41,17 +60,209 @@ const UDEVADM_CMD_OPT: [&str; 2] = ["test-builtin", "net_id"];
 const ID_NET_NAME_ONBOARD: &str = "ID_NET_NAME_ONBOARD";
 const ID_NET_NAME_SLOT: &str = "ID_NET_NAME_SLOT";
 const ID_NET_NAME_PATH: &str = "ID_NET_NAME_PATH";
+/// Default systemd `AlternativeNamesPolicy=` applied alongside the pinned
+/// `Name=`/`AlternativeName=`, so the desired name stays reachable as an
+/// alternative name even when the primary `Name=` match is claimed by
+/// another rule.
+const ALTERNATIVE_NAMES_POLICY: &str = "database onboard slot path mac";
+/// Comment recording, at persist time, the name systemd's own naming
+/// scheme would have picked for this interface absent our pin -- i.e. the
+/// name the pin exists to override. `Verify` compares this baseline
+/// against what the naming scheme predicts now to detect drift caused by
+/// a systemd/naming-scheme upgrade.
+const PERSIST_OVERRIDDEN_NAME_PREFIX: &str = "# nmstate-overridden-name: ";
 
 /// The action to take
 pub(crate) enum PersistAction {
-    /// Persist NIC name state
-    Save,
+    /// Persist NIC name state.
+    ///
+    /// When `link_config` is `true`, also translate the interface's
+    /// captured MTU, MAC override and ethtool pause/ring settings into the
+    /// generated file's `[Link]` section, so the same artifact reproduces
+    /// both the interface name and its low-level device config.
+    Save { link_config: bool },
     /// Print what we would do in Save mode
-    DryRun,
+    DryRun { link_config: bool },
     /// Remove link files not required
     CleanUp,
     /// Print what we would do in clean up mode
     CleanUpDryRun,
+    /// Re-check nmstate-generated link files against the device's current
+    /// predictable name, and re-pin to it -- rewriting `Name=` and the
+    /// `ifname=` karg -- when a naming-scheme change has made it drift.
+    Verify,
+    /// Print what we would do in Verify mode
+    VerifyDryRun,
+}
+
+/// The sysfs-derived selectors available for an interface, used to build
+/// its [`LinkMatch`].
+struct DeviceSelectors {
+    /// Preferably permanent MAC address.
+    mac_address: Option<String>,
+    /// Bus/topological path, e.g. `.../pci0000:00/...`.
+    path: Option<String>,
+    /// The bus the device sits directly on, e.g. `pci`, `usb`, `platform`
+    /// -- the basename of the `device/subsystem` symlink.
+    bus_type: Option<String>,
+    /// Kernel driver name bound to the device.
+    driver: Option<String>,
+    /// Kernel `DEVTYPE`, e.g. `bond`, `bridge`, `vlan`.
+    iface_type: Option<String>,
+    /// The interface's current kernel name.
+    original_name: String,
+}
+
+/// The `[Match]` selectors used to pin an interface's name in a generated
+/// `.link` file. Which of them are set is decided by [`build_link_match`],
+/// which does not set every selector that is available.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct LinkMatch {
+    permanent_mac_address: Option<String>,
+    path: Option<String>,
+    driver: Option<String>,
+    iface_type: Option<String>,
+    original_name: Option<String>,
+}
+
+impl LinkMatch {
+    /// Render as `[Match]` section lines, in systemd.link(5) priority
+    /// order.
+    fn to_match_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(mac) = &self.permanent_mac_address {
+            lines.push(format!("PermanentMACAddress={mac}"));
+        }
+        if let Some(path) = &self.path {
+            lines.push(format!("Path={path}"));
+        }
+        if let Some(driver) = &self.driver {
+            lines.push(format!("Driver={driver}"));
+        }
+        if let Some(iface_type) = &self.iface_type {
+            lines.push(format!("Type={iface_type}"));
+        }
+        if let Some(name) = &self.original_name {
+            lines.push(format!("OriginalName={name}"));
+        }
+        lines
+    }
+}
+
+/// Build the `[Match]` selectors for an interface from whatever
+/// [`DeviceSelectors`] sysfs exposed for it, in systemd.link(5) priority
+/// order.
+///
+/// Mirrors the heuristic Fuchsia's netcfg uses when deciding how to pin a
+/// NIC: devices sitting directly on the `usb` bus are commonly dongles
+/// that renumber their topological path when replugged into a different
+/// port, so the MAC address is preferred for them; devices on a `pci` or
+/// `platform` bus get a path that is stable across reboots and even MAC
+/// reassignment (bonded slaves, SR-IOV virtual functions, some cloud
+/// hypervisors), so the path is preferred there instead. Devices with
+/// neither a MAC nor a bus path (bonds, bridges, VLANs, tun/tap) fall back
+/// to driver/type, and finally the interface's current name, so they can
+/// still be pinned.
+fn build_link_match(selectors: &DeviceSelectors) -> LinkMatch {
+    let mut m = LinkMatch::default();
+
+    let prefer_path = selectors.path.is_some()
+        && matches!(selectors.bus_type.as_deref(), Some("pci") | Some("platform"));
+
+    if prefer_path {
+        m.path = selectors.path.clone();
+    } else if let Some(mac) = &selectors.mac_address {
+        m.permanent_mac_address = Some(mac.clone());
+    }
+
+    if m.path.is_none() && m.permanent_mac_address.is_none() {
+        m.driver = selectors.driver.clone();
+        m.iface_type = selectors.iface_type.clone();
+        if m.driver.is_none() && m.iface_type.is_none() {
+            m.original_name = Some(selectors.original_name.clone());
+        }
+    }
+
+    m
+}
+
+/// Parse the `[Link]` section of a generated link file back into a
+/// [`LinkDeviceConfig`], so `Verify` can carry forward a previously
+/// persisted device config (MTU, MAC override, flow control, ring sizes)
+/// when it re-pins a drifted interface, instead of silently dropping it.
+fn parse_link_device_config(file_path: &Path) -> LinkDeviceConfig {
+    let content = std::fs::read_to_string(file_path).unwrap_or_default();
+    let mut c = LinkDeviceConfig::default();
+    let mut in_link = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(section) =
+            line.strip_prefix('[').and_then(|s| s.strip_suffix(']'))
+        {
+            in_link = section.eq_ignore_ascii_case("Link");
+            continue;
+        }
+        if !in_link {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key {
+            "MACAddress" => c.mac_override = Some(value.to_string()),
+            "MTUBytes" => c.mtu = value.parse().ok(),
+            "RxFlowControl" => c.rx_flow_control = parse_on_off(value),
+            "TxFlowControl" => c.tx_flow_control = parse_on_off(value),
+            "RxBufferSize" => c.rx_ring = value.parse().ok(),
+            "TxBufferSize" => c.tx_ring = value.parse().ok(),
+            _ => {}
+        }
+    }
+    c
+}
+
+fn parse_on_off(value: &str) -> Option<bool> {
+    match value {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse the `[Match]` section of a generated link file back into a
+/// [`LinkMatch`], so `clean_up` can tell which selector(s) -- and
+/// therefore which `ifname=` karg, if any -- were used to pin an
+/// interface, without re-deriving the device topology.
+fn parse_link_match_block(file_path: &Path) -> LinkMatch {
+    let content = std::fs::read_to_string(file_path).unwrap_or_default();
+    let mut m = LinkMatch::default();
+    let mut in_match = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(section) =
+            line.strip_prefix('[').and_then(|s| s.strip_suffix(']'))
+        {
+            in_match = section.eq_ignore_ascii_case("Match");
+            continue;
+        }
+        if !in_match {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key {
+            "PermanentMACAddress" => {
+                m.permanent_mac_address = Some(value.to_string())
+            }
+            "Path" => m.path = Some(value.to_string()),
+            "Driver" => m.driver = Some(value.to_string()),
+            "Type" => m.iface_type = Some(value.to_string()),
+            "OriginalName" => m.original_name = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    m
 }
 
 fn gather_state() -> Result<NetworkState, CliError> {
@@ -62,16 +273,238 @@ fn gather_state() -> Result<NetworkState, CliError> {
     Ok(state)
 }
 
+/// The minimal view of an interface this module needs in order to pin its
+/// name: its kernel name and its (preferably permanent) MAC address, if it
+/// has one. Covers not just physical Ethernet NICs but also bonds,
+/// bridges, VLANs and tun/tap devices, which commonly have no MAC of
+/// their own and rely on [`build_link_match`]'s driver/type/name fallback
+/// instead.
+struct PersistableIface {
+    name: String,
+    mac: Option<String>,
+    /// Device-configuration fields available to translate into the
+    /// generated file's `[Link]` section. Only populated when the
+    /// interface was gathered through the full nmstate state retrieval --
+    /// the netlink fallback only knows the name and MAC.
+    link_config: Option<LinkDeviceConfig>,
+}
+
+/// Device-configuration fields systemd's link-config understands, captured
+/// from the interface's state so they can optionally be written alongside
+/// the `Name=` pin. See systemd.link(5) for `MTUBytes=`, `MACAddress=` and
+/// the corresponding ethtool-driven settings.
+///
+/// Wake-on-LAN is deliberately not among these: nmstate's `EthernetConfig`
+/// doesn't capture the interface's current WoL state, so there is nothing
+/// to translate into `WakeOnLan=` without guessing, and this struct only
+/// ever reflects what was actually read back from the device.
+#[derive(Debug, Clone, Default)]
+struct LinkDeviceConfig {
+    mtu: Option<u64>,
+    mac_override: Option<String>,
+    rx_flow_control: Option<bool>,
+    tx_flow_control: Option<bool>,
+    rx_ring: Option<u32>,
+    tx_ring: Option<u32>,
+}
+
+impl LinkDeviceConfig {
+    fn is_empty(&self) -> bool {
+        self.mtu.is_none()
+            && self.mac_override.is_none()
+            && self.rx_flow_control.is_none()
+            && self.tx_flow_control.is_none()
+            && self.rx_ring.is_none()
+            && self.tx_ring.is_none()
+    }
+
+    /// Render this config as `[Link]` section lines, in the same order
+    /// systemd.link(5) documents them.
+    fn to_link_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(mac) = &self.mac_override {
+            lines.push(format!("MACAddress={mac}"));
+        }
+        if let Some(mtu) = self.mtu {
+            lines.push(format!("MTUBytes={mtu}"));
+        }
+        if let Some(rx) = self.rx_flow_control {
+            lines.push(format!("RxFlowControl={}", on_off(rx)));
+        }
+        if let Some(tx) = self.tx_flow_control {
+            lines.push(format!("TxFlowControl={}", on_off(tx)));
+        }
+        if let Some(rx_ring) = self.rx_ring {
+            lines.push(format!("RxBufferSize={rx_ring}"));
+        }
+        if let Some(tx_ring) = self.tx_ring {
+            lines.push(format!("TxBufferSize={tx_ring}"));
+        }
+        lines
+    }
+}
+
+fn on_off(v: bool) -> &'static str {
+    if v {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// Interface types not worth pinning a name for: they are either not real
+/// NICs at all ([`InterfaceType::Loopback`]) or not modeled well enough by
+/// nmstate to be trusted ([`InterfaceType::Unknown`]).
+fn is_persistable_iface_type(iface_type: &InterfaceType) -> bool {
+    !matches!(
+        iface_type,
+        InterfaceType::Loopback | InterfaceType::Unknown
+    )
+}
+
+/// Enumerate the interfaces worth pinning a name for -- not just physical
+/// Ethernet NICs, but also bonds, bridges, VLANs and tun/tap devices, which
+/// [`build_link_match`]'s driver/type/name fallback exists to cover --
+/// preferring the full nmstate state retrieval (which also validates the
+/// rest of the stack), but falling back to a direct netlink query when that
+/// fails -- e.g. when running against an offline image's chroot where the
+/// usual retrieval path isn't available.
+fn gather_persistable_ifaces() -> Result<Vec<PersistableIface>, CliError> {
+    match gather_state() {
+        Ok(state) => Ok(state
+            .interfaces
+            .iter()
+            .filter(|i| is_persistable_iface_type(&i.iface_type()))
+            .map(|i| PersistableIface {
+                name: i.name().to_string(),
+                mac: i
+                    .base_iface()
+                    .permanent_mac_address
+                    .clone()
+                    .or_else(|| i.base_iface().mac_address.clone()),
+                link_config: Some(gather_link_device_config(i)),
+            })
+            .collect()),
+        Err(e) => {
+            log::warn!(
+                "Failed to retrieve full nmstate network state ({e}), \
+                falling back to a direct netlink query for links"
+            );
+            gather_persistable_ifaces_via_netlink()
+        }
+    }
+}
+
+/// Translate the parts of an interface's captured state that systemd's
+/// link-config can also configure into a [`LinkDeviceConfig`].
+fn gather_link_device_config(iface: &Interface) -> LinkDeviceConfig {
+    let ethtool = match iface {
+        Interface::Ethernet(eth_iface) => eth_iface.ethtool.as_ref(),
+        _ => None,
+    };
+    LinkDeviceConfig {
+        mtu: iface.base_iface().mtu,
+        mac_override: iface.base_iface().mac_address.clone(),
+        rx_flow_control: ethtool
+            .and_then(|e| e.pause.as_ref())
+            .and_then(|p| p.rx),
+        tx_flow_control: ethtool
+            .and_then(|e| e.pause.as_ref())
+            .and_then(|p| p.tx),
+        rx_ring: ethtool.and_then(|e| e.ring.as_ref()).and_then(|r| r.rx),
+        tx_ring: ethtool.and_then(|e| e.ring.as_ref()).and_then(|r| r.tx),
+    }
+}
+
+/// Enumerate links and their permanent MAC addresses directly via netlink,
+/// without spawning any subprocess (e.g. `ip`).
+fn gather_persistable_ifaces_via_netlink(
+) -> Result<Vec<PersistableIface>, CliError> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| {
+            CliError::from(format!(
+                "Failed to start tokio runtime for netlink query: {e}"
+            ))
+        })?
+        .block_on(gather_persistable_ifaces_via_netlink_async())
+}
+
+async fn gather_persistable_ifaces_via_netlink_async(
+) -> Result<Vec<PersistableIface>, CliError> {
+    use netlink_packet_route::link::nlas::Nla;
+
+    let (connection, handle, _) = rtnetlink::new_connection().map_err(|e| {
+        CliError::from(format!("Failed to open netlink connection: {e}"))
+    })?;
+    tokio::spawn(connection);
+
+    let mut ifaces = Vec::new();
+    let mut links = handle.link().get().execute();
+    while let Some(msg) = links.try_next().await.map_err(|e| {
+        CliError::from(format!("Failed to enumerate links via netlink: {e}"))
+    })? {
+        // ARPHRD_LOOPBACK, see <linux/if_arp.h>. Netlink only exposes the
+        // link-layer type here, not nmstate's own `InterfaceType`
+        // classification, so this can't reproduce
+        // `is_persistable_iface_type`'s exact exclusion of
+        // `InterfaceType::{Loopback,Unknown}` -- it only reliably excludes
+        // loopback links. Ethernet, bonds/bridges/VLANs/taps, PPP,
+        // InfiniBand and tunnel (GRE/SIT) interfaces are all kept, so the
+        // fallback's interface set matches the primary path except at the
+        // (rare, non-persistable) margins.
+        const ARPHRD_LOOPBACK: u16 = 772;
+        if msg.header.link_layer_type == ARPHRD_LOOPBACK {
+            continue;
+        }
+        let mut name = None;
+        let mut mac = None;
+        let mut permanent_mac = None;
+        for nla in msg.nlas {
+            match nla {
+                Nla::IfName(n) => name = Some(n),
+                Nla::Address(addr) => mac = Some(format_mac_address(&addr)),
+                Nla::PermAddress(addr) => {
+                    permanent_mac = Some(format_mac_address(&addr))
+                }
+                _ => {}
+            }
+        }
+        if let Some(name) = name {
+            ifaces.push(PersistableIface {
+                name,
+                mac: permanent_mac.or(mac),
+                link_config: None,
+            });
+        }
+    }
+    Ok(ifaces)
+}
+
+fn format_mac_address(raw: &[u8]) -> String {
+    raw.iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
 pub(crate) fn run_persist_immediately(
     root: &str,
     kargsfile: Option<&str>,
     action: PersistAction,
 ) -> Result<String, CliError> {
-    let dry_run = match action {
-        PersistAction::Save => false,
-        PersistAction::DryRun => true,
+    let (dry_run, link_config) = match action {
+        PersistAction::Save { link_config } => (false, link_config),
+        PersistAction::DryRun { link_config } => (true, link_config),
         PersistAction::CleanUp => return clean_up(root, kargsfile, false),
         PersistAction::CleanUpDryRun => return clean_up(root, kargsfile, true),
+        PersistAction::Verify => {
+            return verify_and_refresh(root, kargsfile, false)
+        }
+        PersistAction::VerifyDryRun => {
+            return verify_and_refresh(root, kargsfile, true)
+        }
     };
 
     if is_predictable_ifname_disabled() {
@@ -91,34 +524,45 @@ pub(crate) fn run_persist_immediately(
     }
 
     let mut kargs: Vec<String> = Vec::new();
-    let state = gather_state()?;
+    let ifaces = gather_persistable_ifaces()?;
     let mut changed = false;
-    for iface in state
-        .interfaces
-        .iter()
-        .filter(|i| i.iface_type() == InterfaceType::Ethernet)
-    {
-        // Prefer permanent(often stored in firmware) MAC address
-        let mac = match iface
-            .base_iface()
+    for iface in &ifaces {
+        let iface_name = iface.name.as_str();
+        let selectors = DeviceSelectors {
+            mac_address: iface.mac.clone(),
+            path: device_topological_path(root, iface_name),
+            bus_type: device_bus_type(root, iface_name),
+            driver: read_device_driver(root, iface_name),
+            iface_type: read_device_type(root, iface_name),
+            original_name: iface_name.to_string(),
+        };
+        let link_match = build_link_match(&selectors);
+        // Only a permanent MAC match has a corresponding `ifname=` kernel
+        // argument; the other selectors have no dracut cmdline equivalent.
+        let karg = link_match
             .permanent_mac_address
             .as_deref()
-            .or_else(|| iface.base_iface().mac_address.as_deref())
-        {
-            Some(m) => m,
-            None => continue,
-        };
-        let iface_name = iface.name();
-        let karg = format_ifname_karg(iface_name, mac);
+            .map(|mac| format_ifname_karg(iface_name, mac));
         log::info!(
-            "Will persist the interface {iface_name} with MAC {mac} \
-            using link file and kernel argument {karg}"
+            "Will persist the interface {iface_name} using link file \
+            with match {link_match:?}"
         );
         if !dry_run {
-            changed |=
-                persist_iface_name_via_systemd_link(root, mac, iface_name)?;
-            log::info!("Kernel argument {karg} appended");
-            kargs.push(karg);
+            let link_config = if link_config {
+                iface.link_config.as_ref()
+            } else {
+                None
+            };
+            changed |= persist_iface_name_via_systemd_link(
+                root,
+                &link_match,
+                iface_name,
+                link_config,
+            )?;
+            if let Some(karg) = &karg {
+                log::info!("Kernel argument {karg} appended");
+                kargs.push(karg.clone());
+            }
         }
     }
 
@@ -138,6 +582,116 @@ pub(crate) fn run_persist_immediately(
     Ok("".to_string())
 }
 
+/// Resolve the device's bus/topological path (e.g. `.../pci0000:00/...`),
+/// without spawning any subprocess whenever possible: first the udev
+/// on-disk database's `ID_PATH` property, then the `device` symlink under
+/// `/sys/class/net/<iface_name>/`, and only as a last resort -- e.g. udevd
+/// hasn't processed this device yet -- `udevadm` itself (optionally via
+/// `chroot`). Mirrors the sysfs/udev-db-first, `udevadm`-last pattern used
+/// by [`get_systemd_preferred_iface_name`].
+fn device_topological_path(root: &str, iface_name: &str) -> Option<String> {
+    if let Some(id_path) = read_udev_db_property(root, iface_name, "ID_PATH")
+    {
+        return Some(id_path);
+    }
+    let device_link =
+        Path::new(root).join(format!("sys/class/net/{iface_name}/device"));
+    if let Some(path) = std::fs::read_link(&device_link)
+        .ok()
+        .map(|p| p.to_string_lossy().into_owned())
+    {
+        return Some(path);
+    }
+    udev_id_path(root, iface_name)
+}
+
+/// Read a single `E:<KEY>=value` property from the udev on-disk database
+/// entry for an interface (`/run/udev/data/n<ifindex>`), without spawning
+/// any subprocess -- the same data `udevadm info --query=property` reads.
+fn read_udev_db_property(
+    root: &str,
+    iface_name: &str,
+    key: &str,
+) -> Option<String> {
+    let ifindex: u32 = std::fs::read_to_string(
+        Path::new(root)
+            .join(format!("sys/class/net/{iface_name}/ifindex")),
+    )
+    .ok()?
+    .trim()
+    .parse()
+    .ok()?;
+    let data = std::fs::read_to_string(
+        Path::new(root).join(format!("run/udev/data/n{ifindex}")),
+    )
+    .ok()?;
+    data.lines()
+        .filter_map(|l| l.strip_prefix("E:"))
+        .filter_map(|l| l.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v.to_string())
+}
+
+/// Query udev for the `ID_PATH` property of an interface by invoking
+/// `udevadm` directly, for the rare case the on-disk database doesn't have
+/// it yet. Uses the same `udevadm` invocation convention as
+/// [`get_systemd_preferred_iface_name`].
+fn udev_id_path(root: &str, iface_name: &str) -> Option<String> {
+    let mut cmd = if root == "/" {
+        std::process::Command::new("udevadm")
+    } else {
+        std::process::Command::new("chroot")
+    };
+    if root != "/" {
+        cmd.arg(root).arg("udevadm");
+    }
+    cmd.args(["info", "--query=property"])
+        .arg(format!("/sys/class/net/{iface_name}"));
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let output = String::from_utf8(output.stdout).ok()?;
+    output
+        .lines()
+        .find_map(|l| l.strip_prefix("ID_PATH=").map(ToOwned::to_owned))
+}
+
+/// Resolve the bus an interface's device sits directly on (e.g. `pci`,
+/// `usb`, `platform`), from the basename of the `device/subsystem` symlink
+/// under `/sys/class/net/<iface_name>/`.
+fn device_bus_type(root: &str, iface_name: &str) -> Option<String> {
+    let subsystem_link = Path::new(root)
+        .join(format!("sys/class/net/{iface_name}/device/subsystem"));
+    std::fs::read_link(&subsystem_link)
+        .ok()?
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+}
+
+/// Resolve the kernel driver name bound to an interface, from the
+/// `device/driver` symlink under `/sys/class/net/<iface_name>/`.
+fn read_device_driver(root: &str, iface_name: &str) -> Option<String> {
+    let driver_link = Path::new(root)
+        .join(format!("sys/class/net/{iface_name}/device/driver"));
+    std::fs::read_link(&driver_link)
+        .ok()?
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+}
+
+/// Resolve the kernel `DEVTYPE` of an interface (e.g. `bond`, `bridge`,
+/// `vlan`) from its sysfs `uevent` file. Plain physical NICs typically
+/// don't carry a `DEVTYPE`.
+fn read_device_type(root: &str, iface_name: &str) -> Option<String> {
+    let uevent_path =
+        Path::new(root).join(format!("sys/class/net/{iface_name}/uevent"));
+    std::fs::read_to_string(uevent_path)
+        .ok()?
+        .lines()
+        .find_map(|l| l.strip_prefix("DEVTYPE=").map(ToOwned::to_owned))
+}
+
 fn gen_link_file_path(root: &str, iface_name: &str) -> PathBuf {
     let link_dir = Path::new(root).join(SYSTEMD_NETWORK_LINK_FOLDER);
 
@@ -195,20 +749,6 @@ pub(crate) fn clean_up(
         return Ok("".to_string());
     }
 
-    let state = gather_state()?;
-    let macs: HashMap<&str, &str> = state
-        .interfaces
-        .iter()
-        .filter(|i| i.iface_type() == InterfaceType::Ethernet)
-        .filter_map(|i| {
-            i.base_iface()
-                .permanent_mac_address
-                .as_deref()
-                .or_else(|| i.base_iface().mac_address.as_deref())
-                .map(|m| (i.name(), m))
-        })
-        .collect();
-
     let mut kargs: Vec<String> = Vec::new();
     for (iface_name, file_path) in pinned_ifaces {
         if !is_nmstate_generated_systemd_link_file(&file_path) {
@@ -231,16 +771,14 @@ pub(crate) fn clean_up(
             };
         if systemd_iface_name == iface_name {
             log::info!("Interface name {iface_name} is unchanged");
-            let mac = match macs.get(iface_name.as_str()) {
-                Some(mac) => mac,
-                None => {
-                    log::error!("Interface {iface_name} has no MAC address");
-                    continue;
-                }
-            };
-            let karg = format_ifname_karg(&iface_name, mac);
+            // Regenerate the karg from the match recorded in the file
+            // itself, rather than the interface's current MAC, so it is
+            // removed even if the MAC has since changed.
+            let karg = parse_link_match_block(&file_path)
+                .permanent_mac_address
+                .map(|mac| format_ifname_karg(&iface_name, &mac));
             log::info!(
-                "Will remove generated file {} and kernel argument {karg}",
+                "Will remove generated file {}",
                 file_path.display()
             );
             if !dry_run {
@@ -249,8 +787,10 @@ pub(crate) fn clean_up(
                     "Removed systemd network link file {}",
                     file_path.display()
                 );
-                log::info!("Kernel argument {karg} removed");
-                kargs.push(karg);
+                if let Some(karg) = karg {
+                    log::info!("Kernel argument {karg} removed");
+                    kargs.push(karg);
+                }
             }
         } else {
             log::info!(
@@ -272,6 +812,166 @@ pub(crate) fn clean_up(
     Ok("".to_string())
 }
 
+/// Read back the naming-scheme baseline a generated link file was written
+/// with, from its [`PERSIST_OVERRIDDEN_NAME_PREFIX`] comment.
+fn read_overridden_name(file_path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(file_path).ok()?;
+    content
+        .lines()
+        .find_map(|l| l.strip_prefix(PERSIST_OVERRIDDEN_NAME_PREFIX))
+        .map(ToOwned::to_owned)
+}
+
+/// Re-check every nmstate-generated link file against the device's
+/// current predictable name, to catch the case `clean_up` cannot: a
+/// systemd/naming-scheme upgrade that changes what name the device would
+/// get, while the pin keeps forcing its original fixed name.
+///
+/// For each pin, compares the naming scheme's current prediction against
+/// both the pinned `Name=` and the baseline recorded at persist time
+/// ([`PERSIST_OVERRIDDEN_NAME_PREFIX`]):
+///  - current == pinned name: the pin has no effect any more, same as
+///    `clean_up`'s removal case.
+///  - current == recorded baseline: nothing changed since persist time,
+///    the override is still doing exactly what it was meant to.
+///  - neither: the naming scheme drifted to a third name. The device's
+///    identity (its `[Match]` selectors) and any `[Link]` device config a
+///    prior `Save { link_config: true }` wrote (MTU, MAC override, flow
+///    control, ring sizes) haven't changed, only what name it would be
+///    predicted to get, so the pin is re-created under the new predicted
+///    name: the old `.link` file is replaced by one pinning `current_name`
+///    (carrying the old file's `[Match]`/`[Link]` content forward), and its
+///    `ifname=` karg, if any, is regenerated to match. The new file's
+///    baseline is looked up the same way `Save` looks it up, for an
+///    interface named `current_name`; when neither the udev database nor
+///    `udevadm` knows an interface of that name yet, the new file is
+///    written without a baseline. The superseded `ifname=` karg for the
+///    old pinned name, if any, is dropped from `kargsfile` at the same
+///    time, so a stale directive for the old name can't linger alongside
+///    the new one. Once the device carries the new name, a later `Verify`
+///    run finds the prediction equal to the pinned name and reports the
+///    pin as no longer needed, and a later `clean_up` run recognizes it as
+///    no longer needed too.
+fn verify_and_refresh(
+    root: &str,
+    kargsfile: Option<&str>,
+    dry_run: bool,
+) -> Result<String, CliError> {
+    let netdir = Path::new(root).join(SYSTEMD_NETWORK_LINK_FOLDER);
+
+    if !netdir.exists() {
+        log::info!("{} does not exist, nothing to verify", netdir.display());
+        return Ok("".to_string());
+    }
+
+    let mut kargs_to_add: Vec<String> = Vec::new();
+    let mut kargs_to_remove: Vec<String> = Vec::new();
+
+    for e in netdir.read_dir()? {
+        let e = e?;
+        let file_name = if let Some(n) = e.file_name().to_str() {
+            n.to_string()
+        } else {
+            continue;
+        };
+        let Some(pinned_name) = extract_iface_names_from_link_file(&file_name)
+        else {
+            continue;
+        };
+        let file_path = netdir.join(&file_name);
+        if !is_nmstate_generated_systemd_link_file(&file_path) {
+            continue;
+        }
+
+        let current_name =
+            match get_systemd_preferred_iface_name(root, &pinned_name) {
+                Ok(n) => n,
+                Err(e) => {
+                    log::error!(
+                        "Failed to retrieve systemd preferred iface name \
+                        for {pinned_name}: {e}"
+                    );
+                    continue;
+                }
+            };
+        let overridden_name = read_overridden_name(&file_path);
+
+        if current_name == pinned_name {
+            log::info!(
+                "{pinned_name}: naming scheme now predicts the pinned \
+                name; the pin is no longer needed (run clean-up to \
+                remove it)"
+            );
+        } else if overridden_name.as_deref() == Some(current_name.as_str()) {
+            log::info!(
+                "{pinned_name}: naming-scheme prediction is unchanged \
+                since persist time ({current_name}); pin is up to date"
+            );
+        } else {
+            log::warn!(
+                "{pinned_name}: naming scheme now predicts '{current_name}', \
+                which matches neither the pinned name nor the baseline \
+                recorded at persist time ({overridden_name:?}); will \
+                re-pin to '{current_name}'"
+            );
+            let link_match = parse_link_match_block(&file_path);
+            let link_device_config = parse_link_device_config(&file_path);
+            let new_karg = link_match
+                .permanent_mac_address
+                .as_deref()
+                .map(|mac| format_ifname_karg(&current_name, mac));
+            // The karg the file was originally persisted with, now
+            // superseded by `new_karg` and due for removal from
+            // `kargsfile` -- same MAC, old name.
+            let stale_karg = link_match
+                .permanent_mac_address
+                .as_deref()
+                .map(|mac| format_ifname_karg(&pinned_name, mac));
+            if !dry_run {
+                std::fs::remove_file(&file_path)?;
+                persist_iface_name_via_systemd_link(
+                    root,
+                    &link_match,
+                    &current_name,
+                    Some(&link_device_config),
+                )?;
+                log::info!(
+                    "{pinned_name}: re-pinned to '{current_name}', \
+                    replacing {}",
+                    file_path.display()
+                );
+                if let Some(karg) = new_karg {
+                    log::info!("Kernel argument {karg} appended");
+                    kargs_to_add.push(karg);
+                }
+                if let Some(karg) = stale_karg {
+                    log::info!("Kernel argument {karg} superseded, removing");
+                    kargs_to_remove.push(karg);
+                }
+            }
+        }
+    }
+
+    if !dry_run && (!kargs_to_add.is_empty() || !kargs_to_remove.is_empty()) {
+        if let Some(path) = kargsfile {
+            let mut kargs: Vec<String> = std::fs::read_to_string(path)
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(ToOwned::to_owned)
+                .filter(|k| !kargs_to_remove.contains(k))
+                .collect();
+            for karg in kargs_to_add {
+                if !kargs.contains(&karg) {
+                    kargs.push(karg);
+                }
+            }
+            std::fs::write(path, kargs.join(" "))?;
+        }
+    }
+
+    Ok("".to_string())
+}
+
 fn format_ifname_karg(ifname: &str, mac: &str) -> String {
     format!("ifname={ifname}:{mac}")
 }
@@ -285,6 +985,37 @@ fn format_ifname_karg(ifname: &str, mac: &str) -> String {
 pub(crate) fn get_systemd_preferred_iface_name(
     root: &str,
     iface_name: &str,
+) -> Result<String, CliError> {
+    if let Some(name) = get_systemd_preferred_iface_name_from_udev_db(
+        root, iface_name,
+    ) {
+        return Ok(name);
+    }
+    log::info!(
+        "No usable udev database entry for {iface_name}, falling back to \
+        invoking udevadm"
+    );
+    get_systemd_preferred_iface_name_via_udevadm(root, iface_name)
+}
+
+/// Read the `ID_NET_NAME_*` properties udevd already persisted for this
+/// interface in its on-disk database (`/run/udev/data/n<ifindex>`), the
+/// same data `udevadm info` reads, without spawning the `udevadm` binary.
+fn get_systemd_preferred_iface_name_from_udev_db(
+    root: &str,
+    iface_name: &str,
+) -> Option<String> {
+    for key in [ID_NET_NAME_ONBOARD, ID_NET_NAME_SLOT, ID_NET_NAME_PATH] {
+        if let Some(name) = read_udev_db_property(root, iface_name, key) {
+            return Some(name);
+        }
+    }
+    None
+}
+
+fn get_systemd_preferred_iface_name_via_udevadm(
+    root: &str,
+    iface_name: &str,
 ) -> Result<String, CliError> {
     let mut cmd = if root == "/" {
         std::process::Command::new("udevadm")
@@ -334,8 +1065,9 @@ pub(crate) fn get_systemd_preferred_iface_name(
 
 fn persist_iface_name_via_systemd_link(
     root: &str,
-    mac: &str,
+    link_match: &LinkMatch,
     iface_name: &str,
+    link_config: Option<&LinkDeviceConfig>,
 ) -> Result<bool, CliError> {
     let link_dir = Path::new(root).join(SYSTEMD_NETWORK_LINK_FOLDER);
 
@@ -349,8 +1081,40 @@ fn persist_iface_name_via_systemd_link(
         std::fs::create_dir(&link_dir)?;
     }
 
-    let content =
-        format!("{PERSIST_GENERATED_BY}\n[Match]\nMACAddress={mac}\n\n[Link]\nName={iface_name}\n");
+    let match_section = link_match.to_match_lines().join("\n");
+
+    let mut link_section = format!(
+        "Name={iface_name}\n\
+        AlternativeName={iface_name}\n\
+        AlternativeNamesPolicy={ALTERNATIVE_NAMES_POLICY}"
+    );
+    if let Some(link_config) = link_config.filter(|c| !c.is_empty()) {
+        for line in link_config.to_link_lines() {
+            link_section.push('\n');
+            link_section.push_str(&line);
+        }
+    }
+
+    // Best-effort: record what the naming scheme would have called this
+    // interface absent our pin, so `Verify` has a baseline to detect drift
+    // against later. Not fatal if it can't be determined right now.
+    let overridden_name_comment =
+        match get_systemd_preferred_iface_name(root, iface_name) {
+            Ok(name) => format!("{PERSIST_OVERRIDDEN_NAME_PREFIX}{name}\n"),
+            Err(e) => {
+                log::info!(
+                    "Could not determine the naming-scheme baseline for \
+                    {iface_name}, Verify won't be able to detect drift \
+                    for it: {e}"
+                );
+                String::new()
+            }
+        };
+
+    let content = format!(
+        "{PERSIST_GENERATED_BY}\n{overridden_name_comment}\
+        [Match]\n{match_section}\n\n[Link]\n{link_section}\n"
+    );
 
     std::fs::write(&file_path, content.as_bytes()).map_err(|e| {
         CliError::from(format!(
@@ -383,3 +1147,261 @@ fn is_predictable_ifname_disabled() -> bool {
         .map(|c| c.contains("net.ifnames=0"))
         .unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selectors(
+        path: Option<&str>,
+        bus_type: Option<&str>,
+        mac_address: Option<&str>,
+    ) -> DeviceSelectors {
+        DeviceSelectors {
+            mac_address: mac_address.map(ToOwned::to_owned),
+            path: path.map(ToOwned::to_owned),
+            bus_type: bus_type.map(ToOwned::to_owned),
+            driver: None,
+            iface_type: None,
+            original_name: "eth0".to_string(),
+        }
+    }
+
+    // Real-world sysfs `device` symlink targets look like
+    // `/sys/devices/pci0000:00/0000:00:04.0/...`, and udev `ID_PATH`
+    // values are dash-joined tokens like `pci-0000:00:1c.0-usb-0:1:1.0`.
+    // Neither ever contains the literal substrings `/pci/`, `/usb/` or
+    // `/platform/`, which is why this must be driven off the resolved bus
+    // type rather than the path string itself.
+    #[test]
+    fn prefers_path_on_pci_bus() {
+        let m = build_link_match(&selectors(
+            Some("pci-0000:00:1c.0"),
+            Some("pci"),
+            Some("00:11:22:33:44:55"),
+        ));
+        assert_eq!(m.path.as_deref(), Some("pci-0000:00:1c.0"));
+        assert_eq!(m.permanent_mac_address, None);
+    }
+
+    #[test]
+    fn prefers_path_on_platform_bus() {
+        let m = build_link_match(&selectors(
+            Some("platform-fixed"),
+            Some("platform"),
+            Some("00:11:22:33:44:55"),
+        ));
+        assert_eq!(m.path.as_deref(), Some("platform-fixed"));
+        assert_eq!(m.permanent_mac_address, None);
+    }
+
+    #[test]
+    fn prefers_mac_on_usb_bus() {
+        let m = build_link_match(&selectors(
+            Some("pci-0000:00:1c.0-usb-0:1:1.0"),
+            Some("usb"),
+            Some("00:11:22:33:44:55"),
+        ));
+        assert_eq!(m.path, None);
+        assert_eq!(
+            m.permanent_mac_address.as_deref(),
+            Some("00:11:22:33:44:55")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_mac_without_bus_type() {
+        // No `device/subsystem` symlink could be resolved (e.g. a
+        // virtual device with no backing bus device at all).
+        let m = build_link_match(&selectors(
+            None,
+            None,
+            Some("00:11:22:33:44:55"),
+        ));
+        assert_eq!(m.path, None);
+        assert_eq!(
+            m.permanent_mac_address.as_deref(),
+            Some("00:11:22:33:44:55")
+        );
+    }
+
+    #[test]
+    fn device_bus_type_reads_subsystem_symlink_basename() {
+        let tmp = std::env::temp_dir().join(format!(
+            "nmstate-persist-nic-test-{}",
+            std::process::id()
+        ));
+        let net_dir = tmp.join("sys/class/net/eth0/device");
+        std::fs::create_dir_all(&net_dir).unwrap();
+        std::os::unix::fs::symlink(
+            "/sys/bus/pci",
+            net_dir.join("subsystem"),
+        )
+        .unwrap();
+
+        let bus = device_bus_type(tmp.to_str().unwrap(), "eth0");
+
+        std::fs::remove_dir_all(&tmp).unwrap();
+        assert_eq!(bus.as_deref(), Some("pci"));
+    }
+
+    /// A fresh, uniquely-named temp directory to use as a fake `root`.
+    fn test_root(tag: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "nmstate-persist-nic-test-{tag}-{}",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn link_match_round_trips_through_a_match_block() {
+        let root = test_root("match-round-trip");
+        let net_dir = root.join(SYSTEMD_NETWORK_LINK_FOLDER);
+        std::fs::create_dir_all(&net_dir).unwrap();
+
+        let m = LinkMatch {
+            permanent_mac_address: Some("00:11:22:33:44:55".to_string()),
+            path: Some("pci-0000:00:1c.0".to_string()),
+            driver: Some("e1000e".to_string()),
+            iface_type: Some("bond".to_string()),
+            original_name: Some("eth0".to_string()),
+        };
+        let file_path = net_dir.join("98-nmstate-eth0.link");
+        std::fs::write(
+            &file_path,
+            format!(
+                "{PERSIST_GENERATED_BY}\n[Match]\n{}\n\n[Link]\nName=eth0\n",
+                m.to_match_lines().join("\n")
+            ),
+        )
+        .unwrap();
+
+        let parsed = parse_link_match_block(&file_path);
+
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(parsed, m);
+    }
+
+    /// Write a generated link file for `pinned_name`, recording
+    /// `overridden_name` as the persist-time baseline, with a
+    /// `PermanentMACAddress=` match so re-pinning also exercises the
+    /// `ifname=` karg path.
+    fn write_generated_link_file(
+        root: &Path,
+        pinned_name: &str,
+        overridden_name: &str,
+        mac: &str,
+    ) {
+        let net_dir = root.join(SYSTEMD_NETWORK_LINK_FOLDER);
+        std::fs::create_dir_all(&net_dir).unwrap();
+        std::fs::write(
+            net_dir.join(format!("{PERSIST_FILE_PREFIX}-{pinned_name}.link")),
+            format!(
+                "{PERSIST_GENERATED_BY}\n\
+                {PERSIST_OVERRIDDEN_NAME_PREFIX}{overridden_name}\n\
+                [Match]\nPermanentMACAddress={mac}\n\n\
+                [Link]\nName={pinned_name}\n\
+                AlternativeName={pinned_name}\n\
+                AlternativeNamesPolicy={ALTERNATIVE_NAMES_POLICY}\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    /// Make `get_systemd_preferred_iface_name(root, iface_name)` resolve
+    /// to `predicted_name` via the udev on-disk database, without needing
+    /// a real udev or `udevadm`.
+    fn stub_udev_predicted_name(
+        root: &Path,
+        iface_name: &str,
+        ifindex: u32,
+        predicted_name: &str,
+    ) {
+        let net_dir = root.join(format!("sys/class/net/{iface_name}"));
+        std::fs::create_dir_all(&net_dir).unwrap();
+        std::fs::write(net_dir.join("ifindex"), format!("{ifindex}\n"))
+            .unwrap();
+        let udev_dir = root.join("run/udev/data");
+        std::fs::create_dir_all(&udev_dir).unwrap();
+        std::fs::write(
+            udev_dir.join(format!("n{ifindex}")),
+            format!("E:{ID_NET_NAME_ONBOARD}={predicted_name}\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn verify_leaves_pin_alone_when_prediction_matches_pinned_name() {
+        let root = test_root("verify-unchanged");
+        write_generated_link_file(&root, "eth0", "ens3", "00:11:22:33:44:55");
+        stub_udev_predicted_name(&root, "eth0", 10, "eth0");
+
+        let result =
+            verify_and_refresh(root.to_str().unwrap(), None, false);
+
+        let file_path = root
+            .join(SYSTEMD_NETWORK_LINK_FOLDER)
+            .join("98-nmstate-eth0.link");
+        let survived = file_path.exists();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert!(result.is_ok());
+        assert!(survived, "pin should not be touched by Verify itself");
+    }
+
+    #[test]
+    fn verify_leaves_pin_alone_when_prediction_matches_baseline() {
+        let root = test_root("verify-up-to-date");
+        write_generated_link_file(&root, "eth0", "ens3", "00:11:22:33:44:55");
+        stub_udev_predicted_name(&root, "eth0", 11, "ens3");
+
+        let result =
+            verify_and_refresh(root.to_str().unwrap(), None, false);
+
+        let file_path = root
+            .join(SYSTEMD_NETWORK_LINK_FOLDER)
+            .join("98-nmstate-eth0.link");
+        let survived = file_path.exists();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert!(result.is_ok());
+        assert!(survived, "up-to-date pin should be left as-is");
+    }
+
+    #[test]
+    fn verify_repins_and_refreshes_kargs_on_drift() {
+        let root = test_root("verify-drifted");
+        write_generated_link_file(&root, "eth0", "ens3", "00:11:22:33:44:55");
+        stub_udev_predicted_name(&root, "eth0", 12, "enp0s3");
+        let kargsfile = root.join("kargs");
+        std::fs::write(
+            &kargsfile,
+            "ifname=eth0:00:11:22:33:44:55 ifname=eth1:aa:bb:cc:dd:ee:ff",
+        )
+        .unwrap();
+
+        let result = verify_and_refresh(
+            root.to_str().unwrap(),
+            Some(kargsfile.to_str().unwrap()),
+            false,
+        );
+
+        let old_file = root
+            .join(SYSTEMD_NETWORK_LINK_FOLDER)
+            .join("98-nmstate-eth0.link");
+        let new_file = root
+            .join(SYSTEMD_NETWORK_LINK_FOLDER)
+            .join("98-nmstate-enp0s3.link");
+        let old_survived = old_file.exists();
+        let new_content = std::fs::read_to_string(&new_file).ok();
+        let kargs_content = std::fs::read_to_string(&kargsfile).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(result.is_ok());
+        assert!(!old_survived, "stale pin file should be replaced");
+        let new_content = new_content.expect("re-pinned file should exist");
+        assert!(new_content.contains("Name=enp0s3"));
+        assert!(new_content.contains("PermanentMACAddress=00:11:22:33:44:55"));
+        assert!(kargs_content.contains("ifname=enp0s3:00:11:22:33:44:55"));
+        assert!(!kargs_content.contains("ifname=eth0:00:11:22:33:44:55"));
+        assert!(kargs_content.contains("ifname=eth1:aa:bb:cc:dd:ee:ff"));
+    }
+}